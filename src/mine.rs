@@ -1,8 +1,10 @@
+use std::future::Future;
 use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
-use std::sync::Arc;
-use std::time::Instant;
+use std::sync::{Arc, Condvar, Mutex, OnceLock};
+use std::time::{Duration, Instant};
 
 use colored::*;
+use indicatif::ProgressBar;
 use drillx::{
     equix::{self},
     Hash, Solution,
@@ -14,12 +16,17 @@ use ore_api::{
 use ore_utils::AccountDeserialize;
 use rand::Rng;
 use solana_program::pubkey::Pubkey;
-use solana_rpc_client::spinner;
-use solana_sdk::signer::Signer;
+use solana_rpc_client::{nonblocking::rpc_client::RpcClient, spinner};
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction, instruction::Instruction, signature::Signature,
+    signer::Signer, transaction::Transaction,
+};
 
 use crate::{
     args::MineArgs,
+    proof_of_work::{Difficulty, HashRate},
     send_and_confirm::ComputeBudget,
+    transaction_executor::TransactionExecutor,
     utils::{
         amount_u64_to_string, get_clock, get_config, get_updated_proof_with_authority, proof_pubkey,
     },
@@ -29,6 +36,17 @@ use crate::{
 // Define a constant for maximum retry attempts
 const MAX_RETRIES: u8 = 3;
 
+// Default number of retries (after the first attempt) for RPC reads that are not
+// parameterized by args.
+const DEFAULT_RPC_RETRIES: u64 = 5;
+
+// Base backoff between RPC retries; doubles on each failed attempt.
+const RPC_RETRY_BACKOFF: Duration = Duration::from_millis(100);
+
+// Total time the executor path waits for a confirmation, across every retry of
+// one solution, before abandoning the round.
+const EXECUTOR_CONFIRM_TIMEOUT: Duration = Duration::from_secs(30);
+
 impl Miner {
     pub async fn mine(&self, args: MineArgs) {
         // Open account, if needed.
@@ -38,15 +56,46 @@ impl Miner {
         // Check num threads
         self.check_num_cores(args.cores);
 
+        // Spin up a transaction executor when extra RPC endpoints are configured, so
+        // each solution can be raced across every endpoint at once.
+        let executor = if args.rpc_urls.is_empty() {
+            None
+        } else {
+            let mut clients = vec![self.rpc_client.clone()];
+            for url in &args.rpc_urls {
+                clients.push(Arc::new(RpcClient::new(url.clone())));
+            }
+            // Track submissions for exactly as long as we'll wait on them.
+            Some(TransactionExecutor::new(clients, EXECUTOR_CONFIRM_TIMEOUT))
+        };
+
         // Start mining loop
         let mut last_hash_at = 0;
         let mut last_balance = 0;
         loop {
-            // Fetch proof
-            let config = get_config(&self.rpc_client).await;
-            let proof =
+            // Fetch proof, retrying transient RPC failures before giving up the round.
+            let config = match poll_with_retries(args.rpc_retries, || {
+                get_config(&self.rpc_client)
+            })
+            .await
+            {
+                Ok(config) => config,
+                Err(err) => {
+                    eprintln!("{} Failed to fetch config: {:?}", "ERROR".bold().red(), err);
+                    continue;
+                }
+            };
+            let proof = match poll_with_retries(args.rpc_retries, || {
                 get_updated_proof_with_authority(&self.rpc_client, signer.pubkey(), last_hash_at)
-                    .await;
+            })
+            .await
+            {
+                Ok(proof) => proof,
+                Err(err) => {
+                    eprintln!("{} Failed to fetch proof: {:?}", "ERROR".bold().red(), err);
+                    continue;
+                }
+            };
             println!(
                 "\n\nStake: {} ORE\n{}  Multiplier: {:12}x",
                 amount_u64_to_string(proof.balance),
@@ -64,7 +113,13 @@ impl Miner {
             last_balance = proof.balance;
 
             // Calculate cutoff time
-            let cutoff_time = self.get_cutoff(proof, args.buffer_time).await;
+            let cutoff_time = match self.get_cutoff(proof, args.buffer_time).await {
+                Ok(cutoff_time) => cutoff_time,
+                Err(err) => {
+                    eprintln!("{} {}", "ERROR".bold().red(), err);
+                    continue;
+                }
+            };
 
             // Run drillx
             let solution =
@@ -74,7 +129,14 @@ impl Miner {
             // Build instruction set
             let mut ixs = vec![ore_api::instruction::auth(proof_pubkey(signer.pubkey()))];
             let mut compute_budget = 500_000;
-            if self.should_reset(config).await && rand::thread_rng().gen_range(0..100).eq(&0) {
+            let should_reset = match self.should_reset(config).await {
+                Ok(should_reset) => should_reset,
+                Err(err) => {
+                    eprintln!("{} {}", "ERROR".bold().red(), err);
+                    continue;
+                }
+            };
+            if should_reset && rand::thread_rng().gen_range(0..100).eq(&0) {
                 compute_budget += 100_000;
                 ixs.push(ore_api::instruction::reset(signer.pubkey()));
             }
@@ -87,12 +149,55 @@ impl Miner {
                 solution,
             ));
 
-            // Retry mechanism for the transaction submission
+            // Estimate a base prioritization fee from recent network activity over
+            // the accounts this transaction touches.
+            let base_priority_fee = self.dynamic_priority_fee(&ixs, &args).await;
+
+            // Retry mechanism for the transaction submission. Cap the executor path's
+            // total wait to a single confirm window so attempts across retries can't
+            // outlive the round they're mining for.
+            let submit_deadline = Instant::now() + EXECUTOR_CONFIRM_TIMEOUT;
             let mut attempts = 0;
             while attempts < MAX_RETRIES {
-                let result = self
-                    .send_and_confirm(&ixs, ComputeBudget::Fixed(compute_budget), false)
-                    .await;
+                // Pick this attempt's unit price: either a fresh random bid within
+                // `0..=max` (mirroring bench-tps), or the estimate escalated on each
+                // retry so stuck transactions climb toward the cap.
+                let priority_fee = if args.randomize_priority_fee {
+                    rand::thread_rng().gen_range(0..=args.priority_fee_cap)
+                } else {
+                    // Clamp into [floor, cap]; guard against a misconfigured cap
+                    // below the floor so the escalated bid can never undercut it.
+                    let cap = args.priority_fee_cap.max(args.priority_fee);
+                    base_priority_fee
+                        .saturating_mul(attempts as u64 + 1)
+                        .clamp(args.priority_fee, cap)
+                };
+
+                // Prepend a tight compute-unit limit and the computed unit price.
+                let mut priced_ixs = vec![
+                    ComputeBudgetInstruction::set_compute_unit_limit(compute_budget),
+                    ComputeBudgetInstruction::set_compute_unit_price(priority_fee),
+                ];
+                priced_ixs.extend_from_slice(&ixs);
+
+                // We set the compute budget ourselves, so submit with
+                // `ComputeBudget::None` — letting `send_and_confirm` inject its own
+                // budget ixs too would duplicate them and the runtime would reject it.
+                let result = if let Some(executor) = executor.as_ref() {
+                    let remaining = submit_deadline.saturating_duration_since(Instant::now());
+                    if remaining.is_zero() {
+                        eprintln!("Submission time budget exhausted. Abandoning round...");
+                        break;
+                    }
+                    self.send_via_executor(executor, &priced_ixs, remaining)
+                        .await
+                        .map(|_| ())
+                } else {
+                    self.send_and_confirm(&priced_ixs, ComputeBudget::None, false)
+                        .await
+                        .map(|_| ())
+                        .map_err(|e| format!("{:?}", e))
+                };
 
                 match result {
                     Ok(_) => {
@@ -121,137 +226,93 @@ impl Miner {
         cores: u64,
         min_difficulty: u32,
     ) -> Solution {
-        // Dispatch job to each thread
+        // Publish the round's job to the long-lived solver pool. The pool spins up
+        // one pinned worker per core on first use and keeps them parked between
+        // rounds, so we never pay thread-creation or core-pinning cost here again.
         let progress_bar = Arc::new(spinner::new_progress_bar());
-        let global_best_difficulty = Arc::new(AtomicU32::new(0));
-        let global_total_hashes = Arc::new(AtomicU64::new(0));
         progress_bar.set_message("Mining...");
-        let core_ids = core_affinity::get_core_ids().unwrap();
-
-        let start_time = Instant::now();
-
-        let handles: Vec<_> = core_ids
-            .into_iter()
-            .map(|i| {
-                let global_best_difficulty = Arc::clone(&global_best_difficulty);
-                let global_total_hashes = Arc::clone(&global_total_hashes);
-                std::thread::spawn({
-                    let proof = proof.clone();
-                    let progress_bar = progress_bar.clone();
-                    let mut memory = equix::SolverMemory::new();
-                    move || {
-                        // Return if core should not be used
-                        if (i.id as u64).ge(&cores) {
-                            return (0, 0, Hash::default());
-                        }
-
-                        // Pin to core
-                        let _ = core_affinity::set_for_current(i);
-
-                        // Start hashing
-                        let timer = Instant::now();
-                        let mut nonce = u64::MAX.saturating_div(cores).saturating_mul(i.id as u64);
-                        let mut best_nonce = nonce;
-                        let mut best_difficulty = 0;
-                        let mut best_hash = Hash::default();
-                        loop {
-                            // Create hash
-                            if let Ok(hx) = drillx::hash_with_memory(
-                                &mut memory,
-                                &proof.challenge,
-                                &nonce.to_le_bytes(),
-                            ) {
-                                let difficulty = hx.difficulty();
-                                if difficulty > best_difficulty {
-                                    best_nonce = nonce;
-                                    best_difficulty = difficulty;
-                                    best_hash = hx;
-                                    if difficulty > global_best_difficulty.load(Ordering::Relaxed) {
-                                        global_best_difficulty.store(difficulty, Ordering::Relaxed);
-                                    }
-                                }
-                            }
-
-                            // Increment total hash counter
-                            global_total_hashes.fetch_add(1, Ordering::Relaxed);
-
-                            // Exit if time has elapsed
-                            if nonce % 100 == 0 {
-                                let global_best_difficulty =
-                                    global_best_difficulty.load(Ordering::Relaxed);
-                                let total_hashes = global_total_hashes.load(Ordering::Relaxed);
-                                let elapsed_time = start_time.elapsed().as_secs_f64();
-                                let hash_rate = total_hashes as f64 / elapsed_time;
-
-                                if timer.elapsed().as_secs() >= cutoff_time {
-                                    if i.id == 0 {
-                                        progress_bar.set_message(format!(
-                                            "Mining... (difficulty {}, time {}, {:.2} H/s)",
-                                            global_best_difficulty,
-                                            format_duration(
-                                                cutoff_time
-                                                    .saturating_sub(timer.elapsed().as_secs())
-                                                    as u32
-                                            ),
-                                            hash_rate,
-                                        ));
-                                    }
-                                    if global_best_difficulty >= min_difficulty {
-                                        // Mine until min difficulty has been met
-                                        break;
-                                    }
-                                } else if i.id == 0 {
-                                    progress_bar.set_message(format!(
-                                        "Mining... (difficulty {}, time {}, {:.2} H/s)",
-                                        global_best_difficulty,
-                                        format_duration(
-                                            cutoff_time.saturating_sub(timer.elapsed().as_secs())
-                                                as u32
-                                        ),
-                                        hash_rate,
-                                    ));
-                                }
-                            }
-
-                            // Increment nonce
-                            nonce += 1;
-                        }
+        let pool = SOLVER_POOL.get_or_init(SolverPool::new);
+        pool.solve(
+            proof.challenge,
+            cutoff_time,
+            cores,
+            min_difficulty,
+            progress_bar,
+        )
+    }
 
-                        // Return the best nonce
-                        (best_nonce, best_difficulty, best_hash)
-                    }
-                })
-            })
+    // Estimate a base prioritization fee (micro-lamports per compute unit) from the
+    // network's recent activity over the writable accounts in `ixs`. The estimate is
+    // free to climb above the static `priority_fee` floor when the network is
+    // congested; the per-attempt cap (`priority_fee_cap`) is applied by the caller.
+    async fn dynamic_priority_fee(&self, ixs: &[Instruction], args: &MineArgs) -> u64 {
+        // Collect the unique writable accounts the instructions touch.
+        let mut accounts: Vec<Pubkey> = ixs
+            .iter()
+            .flat_map(|ix| ix.accounts.iter())
+            .filter(|meta| meta.is_writable)
+            .map(|meta| meta.pubkey)
             .collect();
-
-        // Join handles and return best nonce
-        let mut best_nonce = 0;
-        let mut best_difficulty = 0;
-        let mut best_hash = Hash::default();
-        for h in handles {
-            if let Ok((nonce, difficulty, hash)) = h.join() {
-                if difficulty > best_difficulty {
-                    best_difficulty = difficulty;
-                    best_nonce = nonce;
-                    best_hash = hash;
+        accounts.sort_unstable();
+        accounts.dedup();
+
+        // Query recent prioritization fees and take the p75 of the samples, falling
+        // back to the static floor when the RPC returns nothing usable.
+        let estimate = match self
+            .rpc_client
+            .get_recent_prioritization_fees(&accounts)
+            .await
+        {
+            Ok(fees) if !fees.is_empty() => {
+                let mut lamports: Vec<u64> = fees
+                    .into_iter()
+                    .map(|f| f.prioritization_fee)
+                    .filter(|f| *f > 0)
+                    .collect();
+                if lamports.is_empty() {
+                    args.priority_fee
+                } else {
+                    lamports.sort_unstable();
+                    percentile(&lamports, 75)
                 }
             }
-        }
-
-        // Calculate final hash rate
-        let total_hashes = global_total_hashes.load(Ordering::Relaxed);
-        let elapsed_time = start_time.elapsed().as_secs_f64();
-        let hash_rate = total_hashes as f64 / elapsed_time;
+            _ => args.priority_fee,
+        };
 
-        // Update log with final hash rate
-        progress_bar.finish_with_message(format!(
-            "Best hash: {} (difficulty {}, {:.2} H/s)",
-            bs58::encode(best_hash.h).into_string(),
-            best_difficulty,
-            hash_rate,
-        ));
+        // Never bid below the user's static floor.
+        estimate.max(args.priority_fee)
+    }
 
-        Solution::new(best_hash.d, best_nonce.to_le_bytes())
+    // Sign `ixs` against a fresh blockhash, fan the transaction out across the
+    // executor's endpoints, and return the first signature the background poller
+    // reports confirmed. Returns an error if nothing lands within the confirm
+    // timeout, clearing the submission so it can't linger into the next round.
+    async fn send_via_executor(
+        &self,
+        executor: &TransactionExecutor,
+        ixs: &[Instruction],
+        timeout: Duration,
+    ) -> Result<Signature, String> {
+        let signer = self.signer();
+        let blockhash = self
+            .rpc_client
+            .get_latest_blockhash()
+            .await
+            .map_err(|e| format!("Failed to fetch blockhash: {:?}", e))?;
+        let tx =
+            Transaction::new_signed_with_payer(ixs, Some(&signer.pubkey()), &[&signer], blockhash);
+
+        // Subscribe before submitting so we never miss a fast confirmation.
+        let mut confirmations = executor.subscribe();
+        executor.submit(&tx).await;
+
+        match tokio::time::timeout(timeout, confirmations.recv()).await {
+            Ok(Ok(signature)) => Ok(signature),
+            _ => {
+                executor.clear().await;
+                Err("Transaction expired before confirmation".to_string())
+            }
+        }
     }
 
     pub fn check_num_cores(&self, cores: u64) {
@@ -265,28 +326,36 @@ impl Miner {
         }
     }
 
-    async fn should_reset(&self, config: Config) -> bool {
-        let clock = get_clock(&self.rpc_client).await;
-        config
+    async fn should_reset(&self, config: Config) -> Result<bool, String> {
+        let clock = poll_with_retries(DEFAULT_RPC_RETRIES, || get_clock(&self.rpc_client))
+            .await
+            .map_err(|e| format!("Failed to fetch clock: {:?}", e))?;
+        Ok(config
             .last_reset_at
             .saturating_add(EPOCH_DURATION)
             .saturating_sub(5) // Buffer
-            .le(&clock.unix_timestamp)
+            .le(&clock.unix_timestamp))
     }
 
-    async fn get_cutoff(&self, proof: Proof, buffer_time: u64) -> u64 {
-        let clock = get_clock(&self.rpc_client).await;
-        proof
+    async fn get_cutoff(&self, proof: Proof, buffer_time: u64) -> Result<u64, String> {
+        let clock = poll_with_retries(DEFAULT_RPC_RETRIES, || get_clock(&self.rpc_client))
+            .await
+            .map_err(|e| format!("Failed to fetch clock: {:?}", e))?;
+        Ok(proof
             .last_hash_at
             .saturating_add(60)
             .saturating_sub(buffer_time as i64)
             .saturating_sub(clock.unix_timestamp)
-            .max(0) as u64
+            .max(0) as u64)
     }
 
     async fn find_bus(&self) -> Pubkey {
-        // Fetch the bus with the largest balance
-        if let Ok(accounts) = self.rpc_client.get_multiple_accounts(&BUS_ADDRESSES).await {
+        // Fetch the bus with the largest balance, retrying transient RPC failures.
+        if let Ok(accounts) = poll_with_retries(DEFAULT_RPC_RETRIES, || {
+            self.rpc_client.get_multiple_accounts(&BUS_ADDRESSES)
+        })
+        .await
+        {
             let mut top_bus_balance: u64 = 0;
             let mut top_bus = BUS_ADDRESSES[0];
             for account in accounts {
@@ -308,12 +377,376 @@ impl Miner {
     }
 }
 
+// Retry a fallible async read up to `retries` times, sleeping with exponential
+// backoff (starting at `RPC_RETRY_BACKOFF` and doubling) between attempts. Logs
+// the remaining retries on each failure that is retried and only surfaces the
+// error once the attempts are exhausted. Modeled on Solana's `poll_get_latest_blockhash`.
+async fn poll_with_retries<F, Fut, T, E>(retries: u64, mut f: F) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    E: std::fmt::Debug,
+{
+    let mut backoff = RPC_RETRY_BACKOFF;
+    let mut remaining = retries;
+    loop {
+        match f().await {
+            Ok(value) => return Ok(value),
+            Err(err) => {
+                if remaining == 0 {
+                    return Err(err);
+                }
+                remaining -= 1;
+                eprintln!(
+                    "{} RPC read failed ({:?}), {} retries remaining",
+                    "WARNING".bold().yellow(),
+                    err,
+                    remaining
+                );
+                tokio::time::sleep(backoff).await;
+                backoff = backoff.saturating_mul(2);
+            }
+        }
+    }
+}
+
+// Long-lived pool of pinned solver threads, shared across every mining round.
+static SOLVER_POOL: OnceLock<SolverPool> = OnceLock::new();
+
+// A unit of work handed to the solver pool for a single round.
+#[derive(Clone)]
+struct Job {
+    challenge: [u8; 32],
+    cutoff_time: u64,
+    min_difficulty: u32,
+    cores: u64,
+    start_time: Instant,
+    progress_bar: Arc<ProgressBar>,
+}
+
+// Mutable state shared between the main thread and the workers, guarded by a
+// single mutex. `generation` is bumped for every published job so parked workers
+// can tell a fresh job from the one they already finished; `active` counts the
+// workers still grinding the current generation.
+struct PoolState {
+    job: Option<Job>,
+    generation: u64,
+    active: usize,
+}
+
+// A pool of worker threads, one pinned to each core, that park on a `Condvar`
+// between rounds. Modeled on Alfis's miner: publish a job, `notify_all()`, and
+// wait for the workers to report completion rather than spawning fresh threads.
+struct SolverPool {
+    state: Arc<(Mutex<PoolState>, Condvar, Condvar)>,
+    results: Arc<Vec<Mutex<(u64, u32, Hash)>>>,
+    global_best_difficulty: Arc<AtomicU32>,
+    global_total_hashes: Arc<AtomicU64>,
+}
+
+impl SolverPool {
+    // Spawn one parked worker per available core. Called once, lazily.
+    fn new() -> Self {
+        let core_ids = core_affinity::get_core_ids().unwrap();
+        let num_workers = core_ids.len();
+
+        let state = Arc::new((
+            Mutex::new(PoolState {
+                job: None,
+                generation: 0,
+                active: 0,
+            }),
+            Condvar::new(), // signals workers that a new job is available
+            Condvar::new(), // signals the main thread that all workers are done
+        ));
+        let results: Arc<Vec<Mutex<(u64, u32, Hash)>>> =
+            Arc::new((0..num_workers).map(|_| Mutex::new((0, 0, Hash::default()))).collect());
+        let global_best_difficulty = Arc::new(AtomicU32::new(0));
+        let global_total_hashes = Arc::new(AtomicU64::new(0));
+
+        for (worker_index, core) in core_ids.into_iter().enumerate() {
+            let state = Arc::clone(&state);
+            let results = Arc::clone(&results);
+            let global_best_difficulty = Arc::clone(&global_best_difficulty);
+            let global_total_hashes = Arc::clone(&global_total_hashes);
+            std::thread::spawn(move || {
+                // Pin to this core once; it stays warm for the process's lifetime.
+                let _ = core_affinity::set_for_current(core);
+                let mut memory = equix::SolverMemory::new();
+                let mut last_generation = 0u64;
+
+                loop {
+                    // Park until the main thread publishes a job we haven't run yet.
+                    let job = {
+                        let (lock, job_cv, _) = &*state;
+                        let mut guard = lock.lock().unwrap();
+                        while guard.generation == last_generation || guard.job.is_none() {
+                            guard = job_cv.wait(guard).unwrap();
+                        }
+                        last_generation = guard.generation;
+                        guard.job.clone().unwrap()
+                    };
+
+                    // Index by the enumerated worker position: `core_affinity` ids are
+                    // not guaranteed to be a contiguous `0..len` range.
+                    let result = solve_job(
+                        &mut memory,
+                        worker_index,
+                        &job,
+                        &global_best_difficulty,
+                        &global_total_hashes,
+                    );
+                    *results[worker_index].lock().unwrap() = result;
+
+                    // Report completion; wake the main thread once the last worker lands.
+                    let (lock, _, done_cv) = &*state;
+                    let mut guard = lock.lock().unwrap();
+                    guard.active -= 1;
+                    if guard.active == 0 {
+                        done_cv.notify_all();
+                    }
+                }
+            });
+        }
+
+        Self {
+            state,
+            results,
+            global_best_difficulty,
+            global_total_hashes,
+        }
+    }
+
+    // Publish a round's job, wait for every worker to finish, and aggregate the
+    // best nonce/difficulty/hash across the per-worker result slots.
+    fn solve(
+        &self,
+        challenge: [u8; 32],
+        cutoff_time: u64,
+        cores: u64,
+        min_difficulty: u32,
+        progress_bar: Arc<ProgressBar>,
+    ) -> Solution {
+        let num_workers = self.results.len();
+        let start_time = Instant::now();
+
+        // Reset the shared counters for the new round.
+        self.global_best_difficulty.store(0, Ordering::Relaxed);
+        self.global_total_hashes.store(0, Ordering::Relaxed);
+
+        // Publish the job and wake every worker.
+        let (lock, job_cv, done_cv) = &*self.state;
+        {
+            let mut guard = lock.lock().unwrap();
+            guard.generation += 1;
+            guard.active = num_workers;
+            guard.job = Some(Job {
+                challenge,
+                cutoff_time,
+                min_difficulty,
+                cores,
+                start_time,
+                progress_bar: progress_bar.clone(),
+            });
+            job_cv.notify_all();
+        }
+
+        // Wait for all workers to finish (each stops at the cutoff on its own).
+        {
+            let mut guard = lock.lock().unwrap();
+            while guard.active > 0 {
+                guard = done_cv.wait(guard).unwrap();
+            }
+        }
+
+        // Aggregate the best result across workers.
+        let mut best_nonce = 0;
+        let mut best_difficulty = 0;
+        let mut best_hash = Hash::default();
+        for slot in self.results.iter() {
+            let (nonce, difficulty, hash) = *slot.lock().unwrap();
+            if difficulty > best_difficulty {
+                best_difficulty = difficulty;
+                best_nonce = nonce;
+                best_hash = hash;
+            }
+        }
+
+        // Calculate final hash rate
+        let total_hashes = self.global_total_hashes.load(Ordering::Relaxed);
+        let elapsed_time = start_time.elapsed().as_secs_f64();
+        let hash_rate = total_hashes as f64 / elapsed_time;
+
+        // Update log with final hash rate
+        progress_bar.finish_with_message(format!(
+            "Best hash: {} (difficulty {}, {:.2} H/s)",
+            bs58::encode(best_hash.h).into_string(),
+            best_difficulty,
+            hash_rate,
+        ));
+
+        Solution::new(best_hash.d, best_nonce.to_le_bytes())
+    }
+}
+
+// Grind nonces for a single worker until the cutoff is reached (and the global
+// min difficulty has been met). Returns this worker's best (nonce, difficulty,
+// hash). Worker 0 owns the progress-bar messages, exactly as before.
+fn solve_job(
+    memory: &mut equix::SolverMemory,
+    worker_index: usize,
+    job: &Job,
+    global_best_difficulty: &AtomicU32,
+    global_total_hashes: &AtomicU64,
+) -> (u64, u32, Hash) {
+    // Skip workers beyond the core count the user has requested.
+    if (worker_index as u64).ge(&job.cores) {
+        return (0, 0, Hash::default());
+    }
+
+    let timer = Instant::now();
+    let mut nonce = u64::MAX
+        .saturating_div(job.cores)
+        .saturating_mul(worker_index as u64);
+    let mut best_nonce = nonce;
+    let mut best_difficulty = 0;
+    let mut best_hash = Hash::default();
+    // Smoothed hash-rate readout and the last window we sampled (worker 0 only).
+    let mut hash_rate = HashRate::with_window(30);
+    let mut last_sample = (0u64, 0f64);
+    loop {
+        // Create hash
+        if let Ok(hx) =
+            drillx::hash_with_memory(memory, &job.challenge, &nonce.to_le_bytes())
+        {
+            let difficulty = hx.difficulty();
+            if difficulty > best_difficulty {
+                best_nonce = nonce;
+                best_difficulty = difficulty;
+                best_hash = hx;
+                if difficulty > global_best_difficulty.load(Ordering::Relaxed) {
+                    global_best_difficulty.store(difficulty, Ordering::Relaxed);
+                }
+            }
+        }
+
+        // Increment total hash counter
+        global_total_hashes.fetch_add(1, Ordering::Relaxed);
+
+        // Exit if time has elapsed
+        if nonce % 100 == 0 {
+            let global_best_difficulty =
+                Difficulty::from_bits(global_best_difficulty.load(Ordering::Relaxed));
+            let total_hashes = global_total_hashes.load(Ordering::Relaxed);
+            let elapsed_time = job.start_time.elapsed().as_secs_f64();
+
+            let min_difficulty = Difficulty::from_bits(job.min_difficulty);
+            let cutoff_reached = timer.elapsed().as_secs() >= job.cutoff_time;
+            if worker_index == 0 {
+                // Fold this window's instantaneous rate into the moving average.
+                let instantaneous = if elapsed_time > last_sample.1 {
+                    total_hashes.saturating_sub(last_sample.0) as f64
+                        / (elapsed_time - last_sample.1)
+                } else {
+                    0.0
+                };
+                let smoothed = hash_rate.record(instantaneous);
+                last_sample = (total_hashes, elapsed_time);
+
+                // Bits still separating the current best from the target (saturates
+                // at zero once the target is met).
+                let remaining = min_difficulty.saturating_sub(global_best_difficulty);
+                job.progress_bar.set_message(format!(
+                    "Mining... (difficulty {}, -{} to target, time {}, {:.2} H/s)",
+                    global_best_difficulty,
+                    remaining,
+                    format_duration(
+                        job.cutoff_time.saturating_sub(timer.elapsed().as_secs()) as u32
+                    ),
+                    smoothed,
+                ));
+            }
+
+            if cutoff_reached && global_best_difficulty >= min_difficulty {
+                // Mine until min difficulty has been met
+                break;
+            }
+        }
+
+        // Increment nonce
+        nonce += 1;
+    }
+
+    // Return the best nonce
+    (best_nonce, best_difficulty, best_hash)
+}
+
 fn calculate_multiplier(balance: u64, top_balance: u64) -> f64 {
+    // Guard against an uninitialized `top_balance` so the ratio can't blow up to NaN/inf.
+    if top_balance == 0 {
+        return 1.0;
+    }
     1.0 + (balance as f64 / top_balance as f64).min(1.0f64)
 }
 
+// Return the `p`th percentile (0..=100) of an already-sorted slice.
+fn percentile(sorted: &[u64], p: u64) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let idx = (sorted.len().saturating_mul(p as usize) / 100).min(sorted.len() - 1);
+    sorted[idx]
+}
+
 fn format_duration(seconds: u32) -> String {
     let minutes = seconds / 60;
     let remaining_seconds = seconds % 60;
     format!("{:02}:{:02}", minutes, remaining_seconds)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn percentile_selects_expected_sample() {
+        let sorted = [1u64, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+        assert_eq!(percentile(&sorted, 75), 8);
+        assert_eq!(percentile(&sorted, 0), 1);
+        assert_eq!(percentile(&sorted, 100), 10); // clamps to the last element
+        assert_eq!(percentile(&[], 75), 0);
+    }
+
+    #[tokio::test]
+    async fn poll_with_retries_succeeds_after_transient_failures() {
+        let attempts = Cell::new(0u32);
+        let result: Result<u32, &str> = poll_with_retries(3, || {
+            let n = attempts.get() + 1;
+            attempts.set(n);
+            async move {
+                if n < 3 {
+                    Err("transient")
+                } else {
+                    Ok(n)
+                }
+            }
+        })
+        .await;
+        assert_eq!(result, Ok(3));
+        assert_eq!(attempts.get(), 3);
+    }
+
+    #[tokio::test]
+    async fn poll_with_retries_errors_after_exhaustion() {
+        let attempts = Cell::new(0u32);
+        let result: Result<u32, &str> = poll_with_retries(2, || {
+            attempts.set(attempts.get() + 1);
+            async move { Err("always") }
+        })
+        .await;
+        assert_eq!(result, Err("always"));
+        // Initial attempt plus two retries.
+        assert_eq!(attempts.get(), 3);
+    }
+}
@@ -0,0 +1,121 @@
+//! Small value types shared by the mining loop.
+//!
+//! Difficulty is clamped at construction and saturates under subtraction, so
+//! difficulty arithmetic can never silently wrap, and hash rate is smoothed through an exponential moving average
+//! so the progress bar stops jittering between sampling windows.
+
+use std::fmt;
+
+/// A proof-of-work difficulty, measured in leading zero bits.
+///
+/// Following the construction discipline from Tari's difficulty-adjustment audit,
+/// every constructor clamps into `[MIN, MAX]` and every combinator saturates, so a
+/// `Difficulty` can never under/overflow no matter what arithmetic it feeds.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Difficulty(u64);
+
+impl Difficulty {
+    /// The minimum representable difficulty.
+    pub const MIN: Difficulty = Difficulty(0);
+
+    /// The maximum representable difficulty: a 256-bit hash has at most 256
+    /// leading zero bits.
+    pub const MAX: Difficulty = Difficulty(256);
+
+    /// Construct a difficulty, clamping the value into `[MIN, MAX]`.
+    pub fn new(value: u64) -> Self {
+        Difficulty(value.clamp(Self::MIN.0, Self::MAX.0))
+    }
+
+    /// Construct from the `u32` the solver reports, clamping into range.
+    pub fn from_bits(bits: u32) -> Self {
+        Difficulty::new(bits as u64)
+    }
+
+    /// Subtract two difficulties, saturating at `MIN` instead of wrapping. Used to
+    /// report how many bits still separate the current best from the target.
+    pub fn saturating_sub(self, other: Difficulty) -> Self {
+        Difficulty(self.0.saturating_sub(other.0))
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// A smoothed hash-rate readout in hashes per second.
+///
+/// Each window's instantaneous rate is folded into an exponential moving average,
+/// so a brief stall or burst does not whipsaw the displayed H/s.
+#[derive(Clone, Copy, Debug)]
+pub struct HashRate {
+    ema: Option<f64>,
+    alpha: f64,
+}
+
+impl HashRate {
+    /// Build an accumulator whose EMA spans roughly `window` samples.
+    pub fn with_window(window: u32) -> Self {
+        HashRate {
+            ema: None,
+            alpha: 2.0 / (window.max(1) as f64 + 1.0),
+        }
+    }
+
+    /// Fold a window's instantaneous rate into the average and return the current
+    /// smoothed value.
+    pub fn record(&mut self, instantaneous: f64) -> f64 {
+        let next = match self.ema {
+            Some(prev) => self.alpha * instantaneous + (1.0 - self.alpha) * prev,
+            None => instantaneous,
+        };
+        self.ema = Some(next);
+        next
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn difficulty_clamps_into_range() {
+        // Values within range are preserved.
+        assert_eq!(Difficulty::from_bits(42), Difficulty::new(42));
+        // Over-large values saturate at MAX rather than wrapping.
+        assert_eq!(Difficulty::new(u64::MAX), Difficulty::MAX);
+        assert_eq!(Difficulty::new(1_000), Difficulty::MAX);
+    }
+
+    #[test]
+    fn difficulty_saturating_sub_floors_at_min() {
+        assert_eq!(
+            Difficulty::from_bits(10).saturating_sub(Difficulty::from_bits(4)),
+            Difficulty::from_bits(6)
+        );
+        // Subtracting a larger difficulty floors at MIN instead of underflowing.
+        assert_eq!(
+            Difficulty::from_bits(4).saturating_sub(Difficulty::from_bits(10)),
+            Difficulty::MIN
+        );
+    }
+
+    #[test]
+    fn hash_rate_single_sample_window_follows_latest_sample() {
+        // With a single-sample window the average equals the latest sample.
+        let mut rate = HashRate::with_window(1);
+        assert_eq!(rate.record(100.0), 100.0);
+        assert_eq!(rate.record(200.0), 200.0);
+    }
+
+    #[test]
+    fn hash_rate_smooths_towards_new_samples() {
+        let mut rate = HashRate::with_window(9); // alpha = 0.2
+        assert_eq!(rate.record(100.0), 100.0); // first sample seeds the average
+        let smoothed = rate.record(200.0);
+        // 0.2 * 200 + 0.8 * 100 = 120, strictly between the two samples.
+        assert!((smoothed - 120.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,132 @@
+//! Multi-endpoint transaction executor with outstanding-signature tracking.
+//!
+//! Modeled on Solana's `accounts-cluster-bench` executor: a signed transaction is
+//! fanned out across every configured RPC endpoint, each accepted signature is
+//! tracked with its submit time, a background task polls statuses in bulk, and
+//! stale submissions are expired. Firing competing submissions to several
+//! endpoints for the same solution lets the miner take whichever lands first near
+//! the cutoff.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig, signature::Signature, transaction::Transaction,
+};
+use tokio::sync::{broadcast, Mutex};
+use tokio::time::interval;
+
+// How often the background task polls the statuses of all in-flight signatures.
+const POLL_INTERVAL: Duration = Duration::from_millis(400);
+
+// A signature we have submitted and are still waiting on.
+struct Outstanding {
+    signature: Signature,
+    submitted_at: Instant,
+}
+
+pub struct TransactionExecutor {
+    clients: Vec<Arc<RpcClient>>,
+    outstanding: Arc<Mutex<Vec<Outstanding>>>,
+    confirmations: broadcast::Sender<Signature>,
+    max_age: Duration,
+}
+
+impl TransactionExecutor {
+    // Build an executor that fans out across the given RPC endpoints and spawn its
+    // background status poller. `max_age` bounds how long a submission is tracked;
+    // callers should set it to match their confirmation timeout so an aged-out
+    // signature and a timed-out wait agree on when a submission is dead.
+    pub fn new(clients: Vec<Arc<RpcClient>>, max_age: Duration) -> Self {
+        let outstanding = Arc::new(Mutex::new(Vec::new()));
+        let (confirmations, _) = broadcast::channel(64);
+        let executor = Self {
+            clients,
+            outstanding,
+            confirmations,
+            max_age,
+        };
+        executor.spawn_poller();
+        executor
+    }
+
+    // Background task: poll the statuses of all in-flight signatures in bulk, wake
+    // any waiter on the first confirmation, and expire stale submissions.
+    fn spawn_poller(&self) {
+        let clients = self.clients.clone();
+        let outstanding = Arc::clone(&self.outstanding);
+        let confirmations = self.confirmations.clone();
+        let max_age = self.max_age;
+        tokio::spawn(async move {
+            let mut ticker = interval(POLL_INTERVAL);
+            loop {
+                ticker.tick().await;
+
+                let sigs: Vec<Signature> = {
+                    let mut guard = outstanding.lock().await;
+                    let now = Instant::now();
+                    guard.retain(|o| now.duration_since(o.submitted_at) < max_age);
+                    guard.iter().map(|o| o.signature).collect()
+                };
+                if sigs.is_empty() {
+                    continue;
+                }
+
+                // Any endpoint can answer the bulk status query.
+                for client in &clients {
+                    if let Ok(statuses) = client.get_signature_statuses(&sigs).await {
+                        for (status, sig) in statuses.value.into_iter().zip(sigs.iter()) {
+                            if let Some(status) = status {
+                                if status.satisfies_commitment(CommitmentConfig::confirmed()) {
+                                    // Only a landed *and successful* tx counts: a
+                                    // confirmed-but-failed tx (bad solution, drained
+                                    // bus, reset race) must not be reported as success.
+                                    // Drop it either way so a failed submission expires
+                                    // and the caller times out and retries.
+                                    if status.err.is_none() {
+                                        let _ = confirmations.send(*sig);
+                                    }
+                                    outstanding.lock().await.retain(|o| o.signature != *sig);
+                                }
+                            }
+                        }
+                        break;
+                    }
+                }
+            }
+        });
+    }
+
+    // Subscribe to confirmations before submitting so no landing is missed.
+    pub fn subscribe(&self) -> broadcast::Receiver<Signature> {
+        self.confirmations.subscribe()
+    }
+
+    // Fan a signed transaction out to every endpoint, tracking each accepted
+    // signature. The signature is deterministic across endpoints, so it is recorded
+    // at most once no matter how many endpoints accept it.
+    pub async fn submit(&self, tx: &Transaction) {
+        let submitted_at = Instant::now();
+
+        // Fire every endpoint concurrently so one slow RPC can't stall the fan-out;
+        // only take the lock once the round-trips have all resolved.
+        let sends = self.clients.iter().map(|client| client.send_transaction(tx));
+        let results = futures::future::join_all(sends).await;
+
+        let mut outstanding = self.outstanding.lock().await;
+        for signature in results.into_iter().flatten() {
+            if outstanding.iter().all(|o| o.signature != signature) {
+                outstanding.push(Outstanding {
+                    signature,
+                    submitted_at,
+                });
+            }
+        }
+    }
+
+    // Drop every tracked submission, e.g. once a caller has given up waiting.
+    pub async fn clear(&self) {
+        self.outstanding.lock().await.clear();
+    }
+}